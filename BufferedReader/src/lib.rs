@@ -1,22 +1,146 @@
+// `Read::read_buf` and `BorrowedCursor` are still nightly-only; only turn
+// them on when the `nightly_read_buf` feature is requested so the crate
+// keeps building on stable otherwise.
+#![cfg_attr(feature = "nightly_read_buf", feature(read_buf, core_io_borrowed_buf))]
+
 use std::io::prelude::*;
 
 pub const DEFAULT_BUF_SIZE: usize = 8196;
 
-use std::io::{self};
+use std::io::{self, SeekFrom};
 
 use std::cmp;
 use std::fmt;
+use std::mem::MaybeUninit;
+use std::ops::Deref;
 
 extern crate log;
 
+#[cfg(feature = "ringbuf")]
+extern crate libc;
+
+#[cfg(feature = "ringbuf")]
+mod ringbuf;
+
+/// Backing storage for a `BufferedReader`.
+///
+/// `Boxed` is the portable default. `Mirrored` (behind the `ringbuf`
+/// feature) maps the same physical pages twice back-to-back so a window
+/// that logically wraps past the end of the buffer is still one
+/// contiguous slice, which lets `fill_buf` advance pointers instead of
+/// memmove-ing retained bytes on every partial refill.
+enum Storage {
+    /// `initialized` tracks how many leading bytes of `buf` are initialized
+    /// memory: bytes either written by `inner` or zero-filled by
+    /// `init_upto`. Growing the buffer carries that prefix over and later
+    /// refills don't zero it again; the first `init_upto` past the prefix
+    /// still zero-fills up to the length it is asked for, even if `inner`
+    /// then returns fewer bytes.
+    Boxed { buf: Box<[MaybeUninit<u8>]>, initialized: usize },
+    #[cfg(feature = "ringbuf")]
+    Mirrored(ringbuf::MirroredBuffer),
+}
+
+impl Storage {
+    fn boxed(capacity: usize) -> Storage {
+        let buf = vec![MaybeUninit::uninit(); capacity].into_boxed_slice();
+        Storage::Boxed { buf, initialized: 0 }
+    }
+
+    /// The logical capacity of the buffer, i.e. the largest amount of
+    /// data it can hold at once. For mirrored storage this is half of
+    /// the physical (doubled) mapping.
+    fn capacity(&self) -> usize {
+        match self {
+            Storage::Boxed { buf, .. } => buf.len(),
+            #[cfg(feature = "ringbuf")]
+            Storage::Mirrored(m) => m.capacity(),
+        }
+    }
+
+    #[cfg(feature = "ringbuf")]
+    fn is_mirrored(&self) -> bool {
+        matches!(self, Storage::Mirrored(_))
+    }
+
+    #[cfg(not(feature = "ringbuf"))]
+    fn is_mirrored(&self) -> bool {
+        false
+    }
+
+    /// Grows (or shrinks) the buffer to `new_length`, carrying over the
+    /// already-initialized prefix. Mirrored storage is downgraded to
+    /// boxed storage, since the mmap'd mapping can't be resized in place.
+    fn resized(&self, new_length: usize) -> Storage {
+        let mut new_buf = vec![MaybeUninit::uninit(); new_length].into_boxed_slice();
+
+        match self {
+            Storage::Boxed { buf, initialized } => {
+                let keep = buf.len().min(new_length);
+                new_buf[..keep].copy_from_slice(&buf[..keep]);
+                Storage::Boxed { buf: new_buf, initialized: (*initialized).min(new_length) }
+            }
+            #[cfg(feature = "ringbuf")]
+            Storage::Mirrored(m) => {
+                // mmap'd pages are already zero-initialized by the kernel.
+                let src = m.as_slice();
+                let keep = src.len().min(new_length);
+                for (dst, &b) in new_buf[..keep].iter_mut().zip(&src[..keep]) {
+                    *dst = MaybeUninit::new(b);
+                }
+                Storage::Boxed { buf: new_buf, initialized: keep }
+            }
+        }
+    }
+
+    /// Ensures that `buf[..len]` is initialized (zero-filling whatever
+    /// hasn't been written to yet) and returns it as a plain `&mut [u8]`
+    /// so callers can hand it to a `Read::read` implementation.
+    fn init_upto(&mut self, len: usize) -> &mut [u8] {
+        match self {
+            Storage::Boxed { buf, initialized } => {
+                if len > *initialized {
+                    for slot in &mut buf[*initialized..len] {
+                        *slot = MaybeUninit::new(0);
+                    }
+                    *initialized = len;
+                }
+                // SAFETY: buf[..len] was just established to be initialized,
+                // and MaybeUninit<u8> has the same layout as u8.
+                unsafe { std::slice::from_raw_parts_mut(buf.as_mut_ptr() as *mut u8, len) }
+            }
+            #[cfg(feature = "ringbuf")]
+            Storage::Mirrored(m) => &mut m.as_mut_slice()[..len],
+        }
+    }
+}
+
+impl Deref for Storage {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            // SAFETY: buf[..initialized] is, by construction, initialized.
+            Storage::Boxed { buf, initialized } => unsafe {
+                std::slice::from_raw_parts(buf.as_ptr() as *const u8, *initialized)
+            },
+            #[cfg(feature = "ringbuf")]
+            Storage::Mirrored(m) => m.as_slice(),
+        }
+    }
+}
 
 pub struct BufferedReader<R> {
     inner: R,
-    buf: Box<[u8]>,
+    buf: Storage,
     pos: usize,
     cap: usize,
     mark: isize,
-    ahead: usize
+    ahead: usize,
+    /// Ceiling for adaptive growth, set only by `with_adaptive_capacity`.
+    /// `None` means the buffer stays at whatever capacity it was created
+    /// with, as for `new`/`with_capacity`/`with_ringbuf`.
+    grow_max: Option<usize>,
  }
 
  pub trait MarkRead : Read {
@@ -31,12 +155,37 @@ pub struct BufferedReader<R> {
     }
 
     pub fn with_capacity(capacity: usize, inner: R) -> BufferedReader<R> {
-        let mut buffer = Vec::with_capacity(capacity);
-        buffer.resize_with(capacity, Default::default);
-        BufferedReader { inner, buf: buffer.into_boxed_slice(), pos: 0, cap: 0, mark: -1, ahead: 0 }        
+        BufferedReader { inner, buf: Storage::boxed(capacity), pos: 0, cap: 0, mark: -1, ahead: 0, grow_max: None }
     }
 
-    
+    /// Creates a `BufferedReader` backed by a ring buffer that mirrors its
+    /// pages across a doubled virtual mapping, so partial refills never
+    /// need to memmove the retained bytes.
+    ///
+    /// Falls back to the ordinary boxed-slice implementation when the
+    /// platform can't provide the double mapping (e.g. the `memfd_create`
+    /// or `mmap` calls are unavailable or fail).
+    #[cfg(feature = "ringbuf")]
+    pub fn with_ringbuf(capacity: usize, inner: R) -> BufferedReader<R> {
+        let buf = match ringbuf::MirroredBuffer::new(capacity) {
+            Ok(mirrored) => Storage::Mirrored(mirrored),
+            Err(_) => Storage::boxed(capacity),
+        };
+        BufferedReader { inner, buf, pos: 0, cap: 0, mark: -1, ahead: 0, grow_max: None }
+    }
+
+    /// Creates a `BufferedReader` that starts out holding only `min` bytes
+    /// and doubles its capacity, up to `max`, each time a refill finds the
+    /// inner reader still has more to give.
+    ///
+    /// Most streams only ever hold a handful of bytes at a time, so this
+    /// avoids paying for a full `DEFAULT_BUF_SIZE` allocation (and the
+    /// zero-fill that would otherwise go with it) on a reader that's read
+    /// once and dropped.
+    pub fn with_adaptive_capacity(min: usize, max: usize, inner: R) -> BufferedReader<R> {
+        let min = min.min(max);
+        BufferedReader { inner, buf: Storage::boxed(min), pos: 0, cap: 0, mark: -1, ahead: 0, grow_max: Some(max) }
+    }
 }
 
 impl<R> BufferedReader<R> {
@@ -47,9 +196,75 @@ impl<R> BufferedReader<R> {
     
 
     fn resize_buf(&mut self, new_length: usize) -> io::Result<()> {
-        let mut new_buffer = self.buf.to_vec();
-        new_buffer.resize_with(new_length, Default::default);
-        self.buf = new_buffer.into_boxed_slice();
+        self.buf = self.buf.resized(new_length);
+        Ok(())
+    }
+
+    /// Drops whatever is currently buffered, invalidating any outstanding mark.
+    ///
+    /// Every code path that can no longer trust `pos..cap` to agree with the
+    /// underlying stream (a full refill, a seek) should go through here
+    /// instead of resetting the three fields by hand.
+    fn discard_buffer(&mut self) {
+        self.pos = 0;
+        self.cap = 0;
+        self.mark = -1;
+    }
+}
+
+impl<R: Read> BufferedReader<R> {
+    /// Copies the rest of the stream into `dst`, reusing this reader's own
+    /// buffer instead of the fresh, separately-allocated one that a plain
+    /// `io::copy(&mut reader, &mut dst)` would go through.
+    ///
+    /// Any bytes already sitting in `pos..cap` are written out first, since
+    /// `fill_buf` keeps them at the front of the slice it returns, ahead of
+    /// anything it reads from `inner`.
+    pub fn copy_to<W: Write + ?Sized>(&mut self, dst: &mut W) -> io::Result<u64> {
+        let mut written = 0u64;
+
+        loop {
+            let buf = self.fill_buf()?;
+            let len = buf.len();
+            if len == 0 {
+                break;
+            }
+
+            dst.write_all(buf)?;
+
+            written += len as u64;
+            self.consume(len);
+        }
+
+        Ok(written)
+    }
+}
+
+#[cfg(feature = "nightly_read_buf")]
+impl<R: Read> BufferedReader<R> {
+    /// Like the full-refill branch of `fill_buf`, but reads straight into
+    /// our own buffer's uninitialized tail via `Read::read_buf` instead of
+    /// zero-filling it first — the whole point of this feature.
+    fn fill_from_inner_uninit(&mut self) -> io::Result<()> {
+        debug_assert!(self.pos == self.cap);
+        self.discard_buffer();
+
+        match &mut self.buf {
+            Storage::Boxed { buf, initialized } => {
+                let mut borrowed: std::io::BorrowedBuf<'_> = (&mut buf[..]).into();
+                self.inner.read_buf(borrowed.unfilled())?;
+                self.cap = borrowed.len();
+                *initialized = (*initialized).max(self.cap);
+            }
+            #[cfg(feature = "ringbuf")]
+            Storage::Mirrored(m) => {
+                let capacity = m.capacity();
+                let mut borrowed: std::io::BorrowedBuf<'_> = (&mut m.as_mut_slice()[..capacity]).into();
+                self.inner.read_buf(borrowed.unfilled())?;
+                self.cap = borrowed.len();
+            }
+        }
+
         Ok(())
     }
 }
@@ -57,11 +272,15 @@ impl<R> BufferedReader<R> {
 impl<R: Read> Read for BufferedReader<R> {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
 
-        // resize the buffer if needed
-        if buf.len() > self.buf.len() {
-            let _ = self.resize_buf(buf.len());
+        // Nothing buffered and the caller's read is at least as large as
+        // our own capacity: skip the middleman entirely instead of
+        // growing our buffer to match, which would otherwise leave it
+        // permanently inflated for the rest of this reader's life.
+        if self.pos == self.cap && buf.len() >= self.buf.capacity() {
+            self.discard_buffer();
+            return self.inner.read(buf);
         }
-        
+
         // fill the buffer if needed
         if (self.cap - self.pos) < buf.len()  {
             // we need to fill the buffer
@@ -70,7 +289,11 @@ impl<R: Read> Read for BufferedReader<R> {
 
         // there is enough space in the buffer
         // do a simple copy
-        let min_length = std::cmp::min(self.cap, buf.len());
+        //
+        // Note: this is `cap - pos`, not `cap` — mirrored storage doesn't
+        // reset `pos` to zero on a partial refill the way the boxed path
+        // does, so `cap` alone would overrun the valid region.
+        let min_length = std::cmp::min(self.cap - self.pos, buf.len());
         
         // set the indices
         let starting_index = self.pos;
@@ -87,6 +310,37 @@ impl<R: Read> Read for BufferedReader<R> {
 
         return Ok(min_length);
     }
+
+    /// Reads directly into a caller-supplied, possibly-uninitialized
+    /// cursor, without forcing it (or our own buffer) to be zeroed first.
+    ///
+    /// When nothing is currently buffered and the cursor has more room
+    /// than our capacity, this reads straight through into the cursor;
+    /// otherwise it tops the cursor up from whatever is already buffered,
+    /// pulling more from `inner` only as needed.
+    #[cfg(feature = "nightly_read_buf")]
+    fn read_buf(&mut self, mut cursor: std::io::BorrowedCursor<'_>) -> io::Result<()> {
+        if self.pos == self.cap && cursor.capacity() > self.buf.capacity() {
+            self.discard_buffer();
+            return self.inner.read_buf(cursor);
+        }
+
+        // Like `Read::read`, this is a single fill: top the cursor up from
+        // whatever's already buffered, refilling from `inner` at most once
+        // if the buffer is currently empty. A caller that needs the cursor
+        // completely full should loop on `read_buf` itself, same as on
+        // `read`.
+        if self.pos == self.cap {
+            self.fill_from_inner_uninit()?;
+        }
+
+        let available = &self.buf[self.pos..self.cap];
+        let amt = cmp::min(cursor.capacity(), available.len());
+        cursor.append(&available[..amt]);
+        self.consume(amt);
+
+        Ok(())
+    }
 }
 
 impl<R: Read> MarkRead for BufferedReader<R> {
@@ -101,7 +355,7 @@ impl<R: Read> MarkRead for BufferedReader<R> {
     fn mark(&mut self, read_limit: usize) -> io::Result<()> {
         // check if the buffer can hold the read_limit
         // if not then allocate
-        if read_limit > self.buf.len() {
+        if read_limit > self.buf.capacity() {
             let _ = self.resize_buf(read_limit);
         }
 
@@ -124,16 +378,39 @@ impl<R: Read> BufRead for BufferedReader<R> {
         // does the buffer need to be re-filled?
         if self.pos >= self.cap {
             debug_assert!(self.pos == self.cap);
-            self.cap = self.inner.read(&mut self.buf)?;
-            self.pos = 0;
-            self.mark = -1;
+            self.discard_buffer();
+            let capacity = self.buf.capacity();
+            self.cap = self.inner.read(self.buf.init_upto(capacity))?;
+
+            // The inner reader filled us completely, which (for a capacity
+            // this small) is a good sign there's more where that came from.
+            // Grow for next time rather than making every caller pay for a
+            // full-size buffer up front; `mark`/`reset` are untouched since
+            // `discard_buffer` already cleared them above and `resize_buf`
+            // only ever extends the retained prefix.
+            if let Some(max) = self.grow_max {
+                if self.cap == capacity && capacity < max {
+                    let grown = capacity.saturating_mul(2).min(max);
+                    let _ = self.resize_buf(grown);
+                }
+            }
         }
         // we need to do a partial read
-        else {
-            // shift the data from pos to zero
-            self.buf.copy_within(self.pos.., 0);
-            let nread = self.inner.read(&mut self.buf[self.cap..])?;
+        else if self.buf.is_mirrored() {
+            // The mirrored mapping makes `pos..pos+capacity` contiguous
+            // even when it straddles the physical end of the buffer, so
+            // we can just extend `cap` in place instead of compacting.
+            let target_cap = self.pos + self.buf.capacity();
+            let nread = self.inner.read(&mut self.buf.init_upto(target_cap)[self.cap..])?;
             self.cap += nread;
+        } else {
+            // shift the data from pos to zero
+            let retained = self.cap - self.pos;
+            let cap = self.cap;
+            self.buf.init_upto(cap).copy_within(self.pos.., 0);
+            let capacity = self.buf.capacity();
+            let nread = self.inner.read(&mut self.buf.init_upto(capacity)[retained..])?;
+            self.cap = retained + nread;
             self.pos = 0;
             self.mark = -1;
         }
@@ -143,13 +420,66 @@ impl<R: Read> BufRead for BufferedReader<R> {
 
     fn consume(&mut self, amt: usize) {
         self.pos = cmp::min(self.pos + amt, self.cap);
-        
+
         // do we need to invalidate the mark
         if self.mark > -1 {
             if self.pos > self.mark as usize + self.ahead {
                 self.mark = -1;
             }
         }
+
+        // Mirrored storage lets pos/cap run past `capacity` into the
+        // mirrored half; fold them back by a whole capacity once pos has
+        // moved past the first copy so they never run off the mapping.
+        // This is a pointer-bump, not a memmove: the bytes at pos and
+        // pos - capacity are the same physical page. A mark still inside
+        // the old window can't be expressed in the new one, so it's
+        // dropped rather than rebased.
+        if self.buf.is_mirrored() && self.pos >= self.buf.capacity() {
+            let capacity = self.buf.capacity();
+            self.pos -= capacity;
+            self.cap -= capacity;
+            self.mark = -1;
+        }
+    }
+}
+
+impl<R: Read + Seek> BufferedReader<R> {
+    /// Seeks relative to the current position.
+    ///
+    /// If the requested offset still lands inside the currently buffered
+    /// region (`pos..cap`), the buffer is left untouched and only `pos` is
+    /// adjusted. Otherwise the buffer is discarded and the inner reader is
+    /// seeked directly, accounting for the bytes that were buffered but not
+    /// yet consumed.
+    pub fn seek_relative(&mut self, offset: i64) -> io::Result<()> {
+        let pos = self.pos as i64;
+        let cap = self.cap as i64;
+
+        if let Some(new_pos) = pos.checked_add(offset) {
+            if new_pos >= 0 && new_pos <= cap {
+                self.pos = new_pos as usize;
+                return Ok(());
+            }
+        }
+
+        let remaining = cap - pos;
+        self.discard_buffer();
+        self.inner.seek(SeekFrom::Current(-remaining + offset))?;
+        Ok(())
+    }
+}
+
+impl<R: Read + Seek> Seek for BufferedReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        if let SeekFrom::Current(offset) = pos {
+            let remaining = (self.cap - self.pos) as i64;
+            self.discard_buffer();
+            self.inner.seek(SeekFrom::Current(offset - remaining))
+        } else {
+            self.discard_buffer();
+            self.inner.seek(pos)
+        }
     }
 }
 
@@ -160,7 +490,7 @@ where
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt.debug_struct("BufferedReader")
             .field("reader", &self.inner)
-            .field("buffer", &format_args!("{}/{}", self.cap - self.pos, self.buf.len()))
+            .field("buffer", &format_args!("{}/{}", self.cap - self.pos, self.buf.capacity()))
             .finish()
     }
 }
@@ -170,7 +500,7 @@ mod tests {
     use std::io;
     use std::io::prelude::*;
     //use log::Level;
-    //use std::io::{SeekFrom};
+    use std::io::SeekFrom;
     
     use crate::BufferedReader;
 
@@ -211,13 +541,13 @@ mod tests {
         let nread = reader.read(&mut buf);
         assert_eq!(nread.unwrap(), 2);
         assert_eq!(buf, [0, 1]);
-        assert_eq!(reader.buffer(), [2]);
+        assert_eq!(reader.buffer(), []);
 
         let mut buf = [0];
         let nread = reader.read(&mut buf);
         assert_eq!(nread.unwrap(), 1);
         assert_eq!(buf, [2]);
-        assert_eq!(reader.buffer(), []);
+        assert_eq!(reader.buffer(), [3]);
 
         let mut buf = [0, 0, 0];
         let nread = reader.read(&mut buf);
@@ -227,19 +557,24 @@ mod tests {
 
         assert_eq!(reader.read(&mut buf).unwrap(), 0);
     }
-   
+
     #[test]
     fn test_buffered_mark() {
         //env_logger::init();
 
+        // With capacity pinned at 2 (requests larger than capacity now
+        // bypass the buffer only when it's empty), a mark taken right
+        // before an oversized read keeps the buffer non-empty, so that
+        // first read is satisfied from the buffer rather than bypassing,
+        // and returns however many bytes the buffer actually holds.
         let inner: &[u8] = &[5, 6, 7, 0, 1, 2, 3, 4];
         let mut reader = BufferedReader::with_capacity(2, inner);
 
         let _ = reader.mark(2);
         let mut buf = [0, 0, 0];
         let nread = reader.read(&mut buf);
-        assert_eq!(nread.unwrap(), 3);
-        assert_eq!(buf, [5, 6, 7]);
+        assert_eq!(nread.unwrap(), 2);
+        assert_eq!(buf, [5, 6, 0]);
         info!("{:?}", reader.buffer());
         info!("{:?} months in a year.", 12);
         info!("reader [{:?}]", reader);
@@ -249,21 +584,22 @@ mod tests {
         let mut buf = [0, 0];
         let nread = reader.read(&mut buf);
         assert_eq!(nread.unwrap(), 2);
-        assert_eq!(buf, [0, 1]);
-        assert_eq!(reader.buffer(), [2]);
+        assert_eq!(buf, [7, 0]);
+        assert_eq!(reader.buffer(), []);
 
         let mut buf = [0];
         let nread = reader.read(&mut buf);
         assert_eq!(nread.unwrap(), 1);
-        assert_eq!(buf, [2]);
-        assert_eq!(reader.buffer(), []);
+        assert_eq!(buf, [1]);
+        assert_eq!(reader.buffer(), [2]);
 
         let mut buf = [0, 0, 0];
         let nread = reader.read(&mut buf);
         assert_eq!(nread.unwrap(), 2);
-        assert_eq!(buf, [3, 4, 0]);
+        assert_eq!(buf, [2, 3, 0]);
         assert_eq!(reader.buffer(), []);
 
+        assert_eq!(reader.read(&mut buf).unwrap(), 1);
         assert_eq!(reader.read(&mut buf).unwrap(), 0);
     }
 
@@ -272,14 +608,16 @@ mod tests {
     fn test_buffered_reset() {
         //env_logger::init();
 
+        // Same oversized-read-against-a-pinned-2-byte-capacity shape as
+        // `test_buffered_mark`; see the comment there.
         let inner: &[u8] = &[5, 6, 7, 0, 1, 2, 3, 4];
         let mut reader = BufferedReader::with_capacity(2, inner);
 
         let _ = reader.mark(2);
         let mut buf = [0, 0, 0];
         let nread = reader.read(&mut buf);
-        assert_eq!(nread.unwrap(), 3);
-        assert_eq!(buf, [5, 6, 7]);
+        assert_eq!(nread.unwrap(), 2);
+        assert_eq!(buf, [5, 6, 0]);
         info!("{:?}", reader.buffer());
         info!("{:?} months in a year.", 12);
         info!("reader [{:?}]", reader);
@@ -292,8 +630,8 @@ mod tests {
         let mut buf = [0, 0];
         let nread = reader.read(&mut buf);
         assert_eq!(nread.unwrap(), 2);
-        assert_eq!(buf, [0, 1]);
-        assert_eq!(reader.buffer(), [2]);
+        assert_eq!(buf, [7, 0]);
+        assert_eq!(reader.buffer(), []);
 
         // should work
         let _ = reader.reset();
@@ -302,15 +640,15 @@ mod tests {
         buf = [0, 0];
         let nread = reader.read(&mut buf);
         assert_eq!(nread.unwrap(), 2);
-        assert_eq!(buf, [0, 1]);
-        assert_eq!(reader.buffer(), [2]);
+        assert_eq!(buf, [7, 0]);
+        assert_eq!(reader.buffer(), []);
 
 
         let mut buf = [0];
         let nread = reader.read(&mut buf);
         assert_eq!(nread.unwrap(), 1);
-        assert_eq!(buf, [2]);
-        assert_eq!(reader.buffer(), []);
+        assert_eq!(buf, [1]);
+        assert_eq!(reader.buffer(), [2]);
 
         // should do nothing
         let _ = reader.reset();
@@ -318,9 +656,174 @@ mod tests {
         let mut buf = [0, 0, 0];
         let nread = reader.read(&mut buf);
         assert_eq!(nread.unwrap(), 2);
-        assert_eq!(buf, [3, 4, 0]);
+        assert_eq!(buf, [2, 3, 0]);
         assert_eq!(reader.buffer(), []);
 
+        assert_eq!(reader.read(&mut buf).unwrap(), 1);
         assert_eq!(reader.read(&mut buf).unwrap(), 0);
     }
+
+    #[test]
+    fn test_seek_within_buffer_does_not_touch_inner() {
+        use std::io::Cursor;
+
+        let inner = Cursor::new(vec![5, 6, 7, 0, 1, 2, 3, 4]);
+        let mut reader = BufferedReader::with_capacity(4, inner);
+
+        let mut buf = [0, 0];
+        assert_eq!(reader.read(&mut buf).unwrap(), 2);
+        assert_eq!(buf, [5, 6]);
+        assert_eq!(reader.buffer(), [7, 0]);
+
+        // still inside the buffered region, so the buffer should survive
+        reader.seek_relative(1).unwrap();
+        assert_eq!(reader.buffer(), [0]);
+
+        let mut buf = [0];
+        assert_eq!(reader.read(&mut buf).unwrap(), 1);
+        assert_eq!(buf, [0]);
+    }
+
+    #[test]
+    fn test_seek_outside_buffer_discards_and_repositions() {
+        use std::io::Cursor;
+
+        let inner = Cursor::new(vec![5, 6, 7, 0, 1, 2, 3, 4]);
+        let mut reader = BufferedReader::with_capacity(4, inner);
+
+        let mut buf = [0, 0];
+        assert_eq!(reader.read(&mut buf).unwrap(), 2);
+        assert_eq!(buf, [5, 6]);
+
+        reader.seek(SeekFrom::Start(6)).unwrap();
+        assert_eq!(reader.buffer(), []);
+
+        let mut buf = [0, 0];
+        assert_eq!(reader.read(&mut buf).unwrap(), 2);
+        assert_eq!(buf, [3, 4]);
+    }
+
+    #[test]
+    fn test_copy_to_flushes_pending_bytes_then_drains_inner() {
+        let inner: &[u8] = &[5, 6, 7, 0, 1, 2, 3, 4];
+        let mut reader = BufferedReader::with_capacity(4, inner);
+
+        // Prime the buffer with some bytes that have not been consumed yet.
+        let mut buf = [0, 0];
+        assert_eq!(reader.read(&mut buf).unwrap(), 2);
+        assert_eq!(buf, [5, 6]);
+        assert_eq!(reader.buffer(), [7, 0]);
+
+        let mut dst = Vec::new();
+        let copied = reader.copy_to(&mut dst).unwrap();
+        assert_eq!(copied, 6);
+        assert_eq!(dst, vec![7, 0, 1, 2, 3, 4]);
+        assert_eq!(reader.buffer(), []);
+
+        // The stream is exhausted; a second pass copies nothing.
+        let mut dst2 = Vec::new();
+        assert_eq!(reader.copy_to(&mut dst2).unwrap(), 0);
+        assert!(dst2.is_empty());
+    }
+
+    #[test]
+    fn test_adaptive_capacity_doubles_on_full_refills_up_to_max() {
+        let inner: &[u8] = &[0; 100];
+        let mut reader = BufferedReader::with_adaptive_capacity(4, 16, inner);
+        assert_eq!(reader.buf.capacity(), 4);
+
+        // Each of these fully fills the current buffer, so capacity should
+        // double: 4 -> 8 -> 16, then stay pinned at the max.
+        let filled = reader.fill_buf().unwrap().len();
+        assert_eq!(reader.buf.capacity(), 8);
+        reader.consume(filled);
+
+        let filled = reader.fill_buf().unwrap().len();
+        assert_eq!(reader.buf.capacity(), 16);
+        reader.consume(filled);
+
+        let filled = reader.fill_buf().unwrap().len();
+        reader.consume(filled);
+        assert_eq!(reader.buf.capacity(), 16);
+    }
+
+    #[test]
+    fn test_adaptive_capacity_stops_growing_once_inner_returns_less_than_full() {
+        let inner: &[u8] = &[0; 3];
+        let mut reader = BufferedReader::with_adaptive_capacity(4, 64, inner);
+
+        // The inner reader only has 3 bytes, so it never fills the 4-byte
+        // buffer completely and capacity should never grow.
+        let filled = reader.fill_buf().unwrap();
+        assert_eq!(filled.len(), 3);
+        assert_eq!(reader.buf.capacity(), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "ringbuf")]
+    fn test_ringbuf_reads_short_stream_without_losing_bytes() {
+        let inner: &[u8] = &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+        let mut reader = BufferedReader::with_ringbuf(4, inner);
+
+        let mut out = Vec::new();
+        let mut buf = [0; 3];
+        loop {
+            let nread = reader.read(&mut buf).unwrap();
+            if nread == 0 {
+                break;
+            }
+            out.extend_from_slice(&buf[..nread]);
+        }
+
+        assert_eq!(out, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+    }
+
+    #[test]
+    #[cfg(feature = "ringbuf")]
+    fn test_ringbuf_survives_multiple_pointer_bump_wraps() {
+        // The mapping rounds up to a page, so this drives enough bytes
+        // through a small requested capacity to wrap the mirrored window
+        // several times over.
+        let data: Vec<u8> = (0..20_000u32).map(|i| (i % 251) as u8).collect();
+        let mut reader = BufferedReader::with_ringbuf(64, data.as_slice());
+
+        let mut out = Vec::new();
+        let mut buf = [0; 7];
+        loop {
+            let nread = reader.read(&mut buf).unwrap();
+            if nread == 0 {
+                break;
+            }
+            out.extend_from_slice(&buf[..nread]);
+        }
+
+        assert_eq!(out, data);
+    }
+
+    #[test]
+    #[cfg(feature = "nightly_read_buf")]
+    fn test_read_buf_initializes_no_more_than_inner_provided() {
+        use std::io::BorrowedBuf;
+        use std::mem::MaybeUninit;
+
+        let inner: &[u8] = &[1, 2, 3];
+        let mut reader = BufferedReader::with_capacity(64, inner);
+
+        let mut storage = [MaybeUninit::uninit(); 3];
+        let mut borrowed: BorrowedBuf<'_> = (&mut storage[..]).into();
+        reader.read_buf(borrowed.unfilled()).unwrap();
+        assert_eq!(borrowed.filled(), &[1, 2, 3]);
+
+        match &reader.buf {
+            crate::Storage::Boxed { initialized, .. } => {
+                assert!(
+                    *initialized <= 3,
+                    "initialized {} bytes but inner only ever returned 3",
+                    initialized
+                );
+            }
+            #[cfg(feature = "ringbuf")]
+            crate::Storage::Mirrored(_) => unreachable!("with_capacity never produces mirrored storage"),
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,163 @@
+//! A "magic" ring buffer: `capacity` bytes of physical memory mapped twice,
+//! back to back, into one contiguous virtual address range. A logical
+//! window that runs past the end of the first copy reads (and writes)
+//! straight into the second copy, which holds the same physical pages, so
+//! callers never see a seam and never need to memmove data to compact it.
+//!
+//! Only implemented for platforms with `memfd_create` + `mmap` (Linux).
+//! Elsewhere, and on any failure here, callers fall back to the ordinary
+//! boxed-slice storage.
+
+use std::io;
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use std::io;
+    use std::ptr;
+
+    pub struct Mapping {
+        ptr: *mut u8,
+        capacity: usize,
+    }
+
+    unsafe impl Send for Mapping {}
+
+    impl Mapping {
+        pub fn new(capacity: usize) -> io::Result<Mapping> {
+            let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+            let capacity = round_up(capacity.max(1), page_size);
+            let span = capacity * 2;
+
+            unsafe {
+                let fd = libc::memfd_create(c"buffered_reader_ringbuf".as_ptr(), 0);
+                if fd < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+
+                let result = Self::map(fd, capacity, span);
+                libc::close(fd);
+                result
+            }
+        }
+
+        unsafe fn map(fd: libc::c_int, capacity: usize, span: usize) -> io::Result<Mapping> {
+            if libc::ftruncate(fd, capacity as libc::off_t) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            // Reserve a contiguous span first so both halves land next to
+            // each other, then overwrite each half with a shared mapping
+            // of the same file.
+            let base = libc::mmap(
+                ptr::null_mut(),
+                span,
+                libc::PROT_NONE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            );
+            if base == libc::MAP_FAILED {
+                return Err(io::Error::last_os_error());
+            }
+
+            let first = libc::mmap(
+                base,
+                capacity,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED | libc::MAP_FIXED,
+                fd,
+                0,
+            );
+            let second = libc::mmap(
+                base.add(capacity),
+                capacity,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED | libc::MAP_FIXED,
+                fd,
+                0,
+            );
+
+            if first == libc::MAP_FAILED || second == libc::MAP_FAILED {
+                let err = io::Error::last_os_error();
+                libc::munmap(base, span);
+                return Err(err);
+            }
+
+            Ok(Mapping { ptr: base as *mut u8, capacity })
+        }
+
+        pub fn capacity(&self) -> usize {
+            self.capacity
+        }
+
+        pub fn as_slice(&self) -> &[u8] {
+            unsafe { std::slice::from_raw_parts(self.ptr, self.capacity * 2) }
+        }
+
+        pub fn as_mut_slice(&mut self) -> &mut [u8] {
+            unsafe { std::slice::from_raw_parts_mut(self.ptr, self.capacity * 2) }
+        }
+    }
+
+    impl Drop for Mapping {
+        fn drop(&mut self) {
+            unsafe {
+                libc::munmap(self.ptr as *mut libc::c_void, self.capacity * 2);
+            }
+        }
+    }
+
+    fn round_up(value: usize, multiple: usize) -> usize {
+        value.div_ceil(multiple) * multiple
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod sys {
+    use std::io;
+
+    pub struct Mapping;
+
+    impl Mapping {
+        pub fn new(_capacity: usize) -> io::Result<Mapping> {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "mirrored ring buffers require memfd_create and mmap",
+            ))
+        }
+
+        pub fn capacity(&self) -> usize {
+            unreachable!("Mapping::new always fails on this platform")
+        }
+
+        pub fn as_slice(&self) -> &[u8] {
+            unreachable!("Mapping::new always fails on this platform")
+        }
+
+        pub fn as_mut_slice(&mut self) -> &mut [u8] {
+            unreachable!("Mapping::new always fails on this platform")
+        }
+    }
+}
+
+pub struct MirroredBuffer {
+    mapping: sys::Mapping,
+}
+
+impl MirroredBuffer {
+    pub fn new(capacity: usize) -> io::Result<MirroredBuffer> {
+        Ok(MirroredBuffer { mapping: sys::Mapping::new(capacity)? })
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.mapping.capacity()
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        self.mapping.as_slice()
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        self.mapping.as_mut_slice()
+    }
+}